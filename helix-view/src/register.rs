@@ -1,13 +1,42 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
 
 use anyhow::Result;
 use helix_core::hashmap;
 
 use crate::{clipboard::ClipboardType, document::SCRATCH_BUFFER_NAME, Editor};
 
-pub const SPECIAL_REGISTERS: [char; 6] = ['_', '#', '.', '%', '*', '+'];
+pub const SPECIAL_REGISTERS: [char; 20] = [
+    '_', '#', '.', '%', '*', '+', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '^', '/', ':',
+    '-',
+];
+
+/// The number of numbered registers ("1 through "9) that make up the
+/// shifting deleted-text ring. "0 (the most recent yank) is tracked
+/// separately and is never part of the ring.
+const NUMBERED_RING_CAPACITY: usize = 9;
 
 type RegisterValues<'a> = Box<dyn ExactSizeIterator<Item = Cow<'a, str>> + 'a>;
+type RegisterValuesTyped<'a> = Box<dyn ExactSizeIterator<Item = (Cow<'a, str>, Shape)> + 'a>;
+
+/// How yanked or deleted text should be treated when it's later inserted
+/// back into a document: inline with the surrounding text, as one or more
+/// whole lines, or as a rectangular block of columns.
+///
+/// Mirrors Vim's charwise/linewise/blockwise distinction so paste commands
+/// can decide whether to splice inline, open a new line, or insert a
+/// column of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shape {
+    #[default]
+    CharWise,
+    LineWise,
+    BlockWise,
+}
 
 pub trait Register: std::fmt::Debug {
     fn name(&self) -> char;
@@ -15,6 +44,14 @@ pub trait Register: std::fmt::Debug {
 
     fn read<'a>(&'a self, editor: &'a Editor) -> RegisterValues<'a>;
 
+    /// Like [`Register::read`], but pairs each value with the shape it was
+    /// written with. Registers that don't track shape (most of them: they
+    /// hold single-purpose values like a path or a selection index) can
+    /// rely on the default, which reports everything as [`Shape::CharWise`].
+    fn read_typed<'a>(&'a self, editor: &'a Editor) -> RegisterValuesTyped<'a> {
+        Box::new(self.read(editor).map(|value| (value, Shape::CharWise)))
+    }
+
     fn write(&mut self, _editor: &mut Editor, _values: Vec<String>) -> Result<()> {
         Err(anyhow::anyhow!(
             "The '{}' register is not writable",
@@ -22,22 +59,61 @@ pub trait Register: std::fmt::Debug {
         ))
     }
 
+    /// Like [`Register::write`], but tags each value with a [`Shape`].
+    /// Defaults to plain [`Register::write`], discarding the shape, for
+    /// registers that don't care about it.
+    fn write_typed(&mut self, editor: &mut Editor, values: Vec<(String, Shape)>) -> Result<()> {
+        self.write(
+            editor,
+            values.into_iter().map(|(value, _shape)| value).collect(),
+        )
+    }
+
     fn push(&mut self, _editor: &mut Editor, _value: String) -> Result<()> {
         Err(anyhow::anyhow!(
             "The '{}' register is not writable",
             self.name()
         ))
     }
+
+    /// Like [`Register::push`], but tags the value with a [`Shape`].
+    /// Defaults to plain [`Register::push`], discarding the shape, for
+    /// registers that don't care about it.
+    fn push_typed(&mut self, editor: &mut Editor, value: String, _shape: Shape) -> Result<()> {
+        self.push(editor, value)
+    }
+
+    /// Called on the numbered registers (`"0`-`"9`) after a yank or delete
+    /// has been recorded into the numbered ring, so registers that cache a
+    /// derived preview (because their actual storage can't hand out a
+    /// `&str` directly, e.g. [`NumberedRegister`]) can refresh it.
+    /// Most registers have nothing to do here.
+    fn refresh_preview(&mut self) {}
+}
+
+/// Which kind of edit produced a value passed to
+/// [`Registers::write_typed`]/[`Registers::push_typed`], if any.
+///
+/// This lets those methods route yanks and deletes into the numbered
+/// ring (`"0`-`"9`), or a delete with no line ending into the
+/// small-delete register (`"-`), the same way Vim keeps the ring in sync regardless of
+/// which named register the text was also written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterOperation {
+    Yank,
+    Delete,
 }
 
 /// Currently just wraps a `HashMap` of `Register`s.
 #[derive(Debug)]
 pub struct Registers {
     inner: HashMap<char, Box<dyn Register>>,
+    numbered: Rc<RefCell<NumberedRing>>,
 }
 
 impl Registers {
     pub fn get(&self, name: char) -> Option<&dyn Register> {
+        let name = lowercase_register_name(name);
         self.inner.get(&name).map(AsRef::as_ref)
     }
 
@@ -45,24 +121,160 @@ impl Registers {
         self.get(name).map(|reg| reg.read(editor))
     }
 
+    /// Like [`Registers::read`], but pairs each value with the [`Shape`]
+    /// it was written with.
+    pub fn read_typed<'a>(
+        &'a self,
+        name: char,
+        editor: &'a Editor,
+    ) -> Option<RegisterValuesTyped<'a>> {
+        self.get(name).map(|reg| reg.read_typed(editor))
+    }
+
     pub fn write(&mut self, name: char, editor: &mut Editor, values: Vec<String>) -> Result<()> {
+        self.write_typed(
+            name,
+            editor,
+            values
+                .into_iter()
+                .map(|value| (value, Shape::CharWise))
+                .collect(),
+            None,
+        )
+    }
+
+    /// Like [`Registers::write`], but tags each value with a [`Shape`] and
+    /// a [`RegisterOperation`] the write originated from, if any.
+    ///
+    /// Writing to an uppercase register name (`"A`) appends to the
+    /// lowercase register (`"a`) instead of overwriting it, mirroring
+    /// Vim's uppercase-register convention.
+    ///
+    /// Passing `Some(RegisterOperation::Yank)` or
+    /// `Some(RegisterOperation::Delete)` additionally feeds the numbered
+    /// ring (`"0`-`"9`), the same way Vim keeps it in sync regardless of
+    /// which named register the text was also written to; a delete with no
+    /// line ending goes to the small-delete register (`"-`) instead of the
+    /// ring. Only the first
+    /// value is recorded in the ring, mirroring the ring's existing
+    /// single-value-per-slot shape; callers that need every selection
+    /// represented should join them before writing.
+    pub fn write_typed(
+        &mut self,
+        name: char,
+        editor: &mut Editor,
+        values: Vec<(String, Shape)>,
+        operation: Option<RegisterOperation>,
+    ) -> Result<()> {
+        if let Some(operation) = operation {
+            self.record_operation(name, operation, editor, &values)?;
+        }
+
+        if name.is_ascii_uppercase() {
+            let name = lowercase_register_name(name);
+            for (value, shape) in values {
+                self.push_typed(name, editor, value, shape, None)?;
+            }
+            return Ok(());
+        }
+
         if let Some(reg) = self.inner.get_mut(&name) {
-            reg.write(editor, values)
+            reg.write_typed(editor, values)
         } else {
-            let reg = SimpleRegister::new_with_values(name, values);
+            let reg = SimpleRegister::new_with_typed_values(name, values);
             self.inner.insert(name, Box::new(reg));
             Ok(())
         }
     }
 
     pub fn push(&mut self, name: char, editor: &mut Editor, value: String) -> Result<()> {
+        self.push_typed(name, editor, value, Shape::CharWise, None)
+    }
+
+    /// Like [`Registers::push`], but tags the value with a [`Shape`] and,
+    /// like [`Registers::write_typed`], an optional [`RegisterOperation`]
+    /// to feed into the numbered ring.
+    ///
+    /// As with [`Registers::write_typed`], an uppercase register name
+    /// (`"A`) folds to its lowercase register (`"a`).
+    pub fn push_typed(
+        &mut self,
+        name: char,
+        editor: &mut Editor,
+        value: String,
+        shape: Shape,
+        operation: Option<RegisterOperation>,
+    ) -> Result<()> {
+        if let Some(operation) = operation {
+            self.record_operation(
+                name,
+                operation,
+                editor,
+                std::slice::from_ref(&(value.clone(), shape)),
+            )?;
+        }
+
+        let name = lowercase_register_name(name);
+
         if let Some(reg) = self.inner.get_mut(&name) {
-            reg.push(editor, value)
+            reg.push_typed(editor, value, shape)
         } else {
-            self.write(name, editor, vec![value])
+            self.write_typed(name, editor, vec![(value, shape)], None)
         }
     }
 
+    /// Feeds a yank or delete into the numbered ring (`"0`-`"9`), then
+    /// refreshes the cached previews the ring's registers hand out (see
+    /// [`NumberedRegister::refresh_preview`]).
+    ///
+    /// A delete smaller than a line (no embedded line ending, see
+    /// [`is_small_delete`]) is routed to the small-delete register (`"-`)
+    /// instead, keeping it out of the ring so it doesn't shift older
+    /// deletes out of `"9`.
+    ///
+    /// Writes targeting the blackhole register (`"_`) are ignored, as
+    /// `"_d`/`"_y` are meant to discard their input entirely rather than
+    /// still feed the ring or `"-`.
+    fn record_operation(
+        &mut self,
+        name: char,
+        operation: RegisterOperation,
+        editor: &mut Editor,
+        values: &[(String, Shape)],
+    ) -> Result<()> {
+        if lowercase_register_name(name) == '_' {
+            return Ok(());
+        }
+
+        let Some((value, shape)) = values.first() else {
+            return Ok(());
+        };
+
+        match operation {
+            RegisterOperation::Yank => {
+                self.numbered.borrow_mut().yanked = Some((value.clone(), *shape));
+            }
+            RegisterOperation::Delete if is_small_delete(value) => {
+                return self.push_typed('-', editor, value.clone(), *shape, None);
+            }
+            RegisterOperation::Delete => {
+                let mut numbered = self.numbered.borrow_mut();
+                if numbered.deleted.len() == NUMBERED_RING_CAPACITY {
+                    numbered.deleted.pop_back();
+                }
+                numbered.deleted.push_front((value.clone(), *shape));
+            }
+        }
+
+        for name in '0'..='9' {
+            if let Some(reg) = self.inner.get_mut(&name) {
+                reg.refresh_preview();
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn first<'a>(&'a self, name: char, editor: &'a Editor) -> Option<Cow<'a, str>> {
         self.read(name, editor)
             .and_then(|mut entries| entries.next())
@@ -82,6 +294,8 @@ impl Registers {
     }
 
     pub fn remove(&mut self, name: char) -> Option<Box<dyn Register>> {
+        let name = lowercase_register_name(name);
+
         if SPECIAL_REGISTERS.contains(&name) {
             None
         } else {
@@ -90,19 +304,152 @@ impl Registers {
     }
 }
 
+/// Folds an uppercase ASCII register name to its lowercase counterpart so
+/// that `"A` and `"a` refer to the same storage, as in Vim.
+fn lowercase_register_name(name: char) -> char {
+    if name.is_ascii_uppercase() {
+        name.to_ascii_lowercase()
+    } else {
+        name
+    }
+}
+
+/// Whether a delete is small enough to go to the small-delete register
+/// (`"-`) rather than the `"1`-`"9` ring: anything that doesn't span a
+/// whole line, i.e. has no embedded line ending.
+fn is_small_delete(value: &str) -> bool {
+    !value.contains('\n')
+}
+
 impl Default for Registers {
     fn default() -> Self {
+        let numbered = Rc::new(RefCell::new(NumberedRing::default()));
+
         // Prepopulate the special registers.
-        let inner = hashmap!(
+        let mut inner = hashmap!(
             '_' => Box::new(BlackholeRegister::default()) as Box<dyn Register>,
             '#' => Box::new(SelectionIndexRegister::default()),
             '.' => Box::new(SelectionContentsRegister::default()),
             '%' => Box::new(DocumentPathRegister::default()),
             '*' => Box::new(SystemClipboardRegister::default()),
             '+' => Box::new(PrimaryClipboardRegister::default()),
+            '^' => Box::new(LastInsertRegister::default()),
+            '/' => Box::new(LastSearchRegister::default()),
+            ':' => Box::new(LastCommandRegister::default()),
+            '-' => Box::new(SmallDeleteRegister::default()),
         );
 
-        Self { inner }
+        for name in '0'..='9' {
+            inner.insert(
+                name,
+                Box::new(NumberedRegister::new(name, numbered.clone())) as Box<dyn Register>,
+            );
+        }
+
+        Self { inner, numbered }
+    }
+}
+
+/// The shared storage backing the numbered registers `"0`-`"9`.
+///
+/// `"0` always holds the most recent yank. `"1`-`"9` form a ring of the
+/// last nine deletions: each new delete is pushed to the front (becoming
+/// `"1`) and shifts the rest back, dropping whatever was in `"9`.
+#[derive(Debug, Default)]
+struct NumberedRing {
+    yanked: Option<(String, Shape)>,
+    deleted: VecDeque<(String, Shape)>,
+}
+
+impl NumberedRing {
+    fn get(&self, digit: u32) -> Option<&str> {
+        self.get_typed(digit).map(|(value, _shape)| value)
+    }
+
+    /// Like [`NumberedRing::get`], but also returns the [`Shape`] the
+    /// entry was recorded with.
+    fn get_typed(&self, digit: u32) -> Option<(&str, Shape)> {
+        match digit {
+            0 => self
+                .yanked
+                .as_ref()
+                .map(|(value, shape)| (value.as_str(), *shape)),
+            1..=9 => self
+                .deleted
+                .get(digit as usize - 1)
+                .map(|(value, shape)| (value.as_str(), *shape)),
+            _ => None,
+        }
+    }
+}
+
+/// A read-only view onto one slot (`"0`-`"9`) of the shared
+/// [`NumberedRing`].
+///
+/// The ring itself is shared and borrowed on every read, so there's no
+/// owned storage to hand out a `&str` preview from directly; `preview`
+/// caches the slot's first line here instead, kept up to date by
+/// [`NumberedRegister::refresh_preview`].
+#[derive(Debug)]
+struct NumberedRegister {
+    name: char,
+    ring: Rc<RefCell<NumberedRing>>,
+    preview: String,
+}
+
+impl NumberedRegister {
+    fn new(name: char, ring: Rc<RefCell<NumberedRing>>) -> Self {
+        let preview = Self::preview_for(&ring, name);
+        Self {
+            name,
+            ring,
+            preview,
+        }
+    }
+
+    fn digit(&self) -> u32 {
+        self.name.to_digit(10).expect("numbered register name")
+    }
+
+    fn preview_for(ring: &Rc<RefCell<NumberedRing>>, name: char) -> String {
+        let digit = name.to_digit(10).expect("numbered register name");
+        ring.borrow()
+            .get(digit)
+            .and_then(|value| value.lines().next())
+            .unwrap_or("<empty>")
+            .to_string()
+    }
+}
+
+impl Register for NumberedRegister {
+    fn name(&self) -> char {
+        self.name
+    }
+
+    fn preview(&self) -> &str {
+        &self.preview
+    }
+
+    fn read<'a>(&'a self, _editor: &Editor) -> RegisterValues<'a> {
+        let value = self.ring.borrow().get(self.digit()).map(String::from);
+        Box::new(value.into_iter().map(Cow::from))
+    }
+
+    fn read_typed<'a>(&'a self, _editor: &Editor) -> RegisterValuesTyped<'a> {
+        let value = self
+            .ring
+            .borrow()
+            .get_typed(self.digit())
+            .map(|(value, shape)| (value.to_string(), shape));
+        Box::new(
+            value
+                .into_iter()
+                .map(|(value, shape)| (Cow::from(value), shape)),
+        )
+    }
+
+    fn refresh_preview(&mut self) {
+        self.preview = Self::preview_for(&self.ring, self.name);
     }
 }
 
@@ -112,14 +459,25 @@ impl Default for Registers {
 #[derive(Debug, Default)]
 struct SimpleRegister {
     name: char,
-    /// Saved selections or history values.
+    /// Saved selections or history values, each tagged with the `Shape`
+    /// it was written with.
     /// These are stored in reverse order to make pushing new values to
     /// the "beginning" of the preview efficient.
-    values: Vec<String>,
+    values: Vec<(String, Shape)>,
 }
 
 impl SimpleRegister {
-    fn new_with_values(name: char, mut values: Vec<String>) -> Self {
+    fn new_with_values(name: char, values: Vec<String>) -> Self {
+        Self::new_with_typed_values(
+            name,
+            values
+                .into_iter()
+                .map(|value| (value, Shape::CharWise))
+                .collect(),
+        )
+    }
+
+    fn new_with_typed_values(name: char, mut values: Vec<(String, Shape)>) -> Self {
         values.reverse();
         Self { name, values }
     }
@@ -133,22 +491,50 @@ impl Register for SimpleRegister {
     fn preview(&self) -> &str {
         self.values
             .last()
-            .and_then(|s| s.lines().next())
+            .and_then(|(value, _shape)| value.lines().next())
             .unwrap_or("<empty>")
     }
 
     fn read<'a>(&'a self, _editor: &Editor) -> RegisterValues<'a> {
-        Box::new(self.values.iter().map(Cow::from).rev())
+        Box::new(
+            self.values
+                .iter()
+                .map(|(value, _shape)| Cow::from(value))
+                .rev(),
+        )
+    }
+
+    fn read_typed<'a>(&'a self, _editor: &Editor) -> RegisterValuesTyped<'a> {
+        Box::new(
+            self.values
+                .iter()
+                .map(|(value, shape)| (Cow::from(value), *shape))
+                .rev(),
+        )
     }
 
     fn write(&mut self, _editor: &mut Editor, values: Vec<String>) -> Result<()> {
+        self.values = values
+            .into_iter()
+            .map(|value| (value, Shape::CharWise))
+            .collect();
+        self.values.reverse();
+        Ok(())
+    }
+
+    fn write_typed(&mut self, _editor: &mut Editor, values: Vec<(String, Shape)>) -> Result<()> {
         self.values = values;
         self.values.reverse();
         Ok(())
     }
 
     fn push(&mut self, _editor: &mut Editor, value: String) -> Result<()> {
-        self.values.push(value);
+        self.values.push((value, Shape::CharWise));
+        Ok(())
+    }
+
+    fn push_typed(&mut self, _editor: &mut Editor, value: String, shape: Shape) -> Result<()> {
+        self.values.push((value, shape));
         Ok(())
     }
 }
@@ -247,6 +633,145 @@ impl Register for DocumentPathRegister {
     }
 }
 
+/// Holds the text most recently typed in insert mode. Populated by
+/// `write`/`push` from the insert-mode-exit command, which this series
+/// doesn't wire up yet.
+#[derive(Debug, Default)]
+struct LastInsertRegister {
+    value: Option<String>,
+}
+
+impl Register for LastInsertRegister {
+    fn name(&self) -> char {
+        '^'
+    }
+
+    fn preview(&self) -> &str {
+        self.value
+            .as_deref()
+            .and_then(|value| value.lines().next())
+            .unwrap_or("<empty>")
+    }
+
+    fn read<'a>(&'a self, _editor: &Editor) -> RegisterValues<'a> {
+        Box::new(self.value.as_deref().map(Cow::from).into_iter())
+    }
+
+    fn write(&mut self, _editor: &mut Editor, mut values: Vec<String>) -> Result<()> {
+        self.value = values.pop();
+        Ok(())
+    }
+
+    fn push(&mut self, _editor: &mut Editor, value: String) -> Result<()> {
+        self.value = Some(value);
+        Ok(())
+    }
+}
+
+/// Holds the editor's current search pattern. Writable (so `search.register`
+/// and friends can still target `"/`) but not yet populated by a search
+/// command in this series.
+#[derive(Debug, Default)]
+struct LastSearchRegister {
+    value: Option<String>,
+}
+
+impl Register for LastSearchRegister {
+    fn name(&self) -> char {
+        '/'
+    }
+
+    fn preview(&self) -> &str {
+        self.value
+            .as_deref()
+            .and_then(|value| value.lines().next())
+            .unwrap_or("<empty>")
+    }
+
+    fn read<'a>(&'a self, _editor: &Editor) -> RegisterValues<'a> {
+        Box::new(self.value.as_deref().map(Cow::from).into_iter())
+    }
+
+    fn write(&mut self, _editor: &mut Editor, mut values: Vec<String>) -> Result<()> {
+        self.value = values.pop();
+        Ok(())
+    }
+
+    fn push(&mut self, _editor: &mut Editor, value: String) -> Result<()> {
+        self.value = Some(value);
+        Ok(())
+    }
+}
+
+/// Holds the last command line executed through the command prompt.
+/// Populated by `write`/`push` from the command prompt, which this series
+/// doesn't wire up yet.
+#[derive(Debug, Default)]
+struct LastCommandRegister {
+    value: Option<String>,
+}
+
+impl Register for LastCommandRegister {
+    fn name(&self) -> char {
+        ':'
+    }
+
+    fn preview(&self) -> &str {
+        self.value
+            .as_deref()
+            .and_then(|value| value.lines().next())
+            .unwrap_or("<empty>")
+    }
+
+    fn read<'a>(&'a self, _editor: &Editor) -> RegisterValues<'a> {
+        Box::new(self.value.as_deref().map(Cow::from).into_iter())
+    }
+
+    fn write(&mut self, _editor: &mut Editor, mut values: Vec<String>) -> Result<()> {
+        self.value = values.pop();
+        Ok(())
+    }
+
+    fn push(&mut self, _editor: &mut Editor, value: String) -> Result<()> {
+        self.value = Some(value);
+        Ok(())
+    }
+}
+
+/// Holds the most recent delete smaller than a line, kept out of the
+/// `"1`-`"9` ring (see [`is_small_delete`]).
+#[derive(Debug, Default)]
+struct SmallDeleteRegister {
+    value: Option<String>,
+}
+
+impl Register for SmallDeleteRegister {
+    fn name(&self) -> char {
+        '-'
+    }
+
+    fn preview(&self) -> &str {
+        self.value
+            .as_deref()
+            .and_then(|value| value.lines().next())
+            .unwrap_or("<empty>")
+    }
+
+    fn read<'a>(&'a self, _editor: &Editor) -> RegisterValues<'a> {
+        Box::new(self.value.as_deref().map(Cow::from).into_iter())
+    }
+
+    fn write(&mut self, _editor: &mut Editor, mut values: Vec<String>) -> Result<()> {
+        self.value = values.pop();
+        Ok(())
+    }
+
+    fn push(&mut self, _editor: &mut Editor, value: String) -> Result<()> {
+        self.value = Some(value);
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default)]
 struct SystemClipboardRegister {
     values: Vec<String>,
@@ -265,6 +790,10 @@ impl Register for SystemClipboardRegister {
         read_from_clipboard(&self.values, editor, ClipboardType::Clipboard)
     }
 
+    fn read_typed<'a>(&'a self, editor: &'a Editor) -> RegisterValuesTyped<'a> {
+        read_typed_from_clipboard(&self.values, editor, ClipboardType::Clipboard)
+    }
+
     fn write(&mut self, editor: &mut Editor, values: Vec<String>) -> Result<()> {
         self.values = values;
         save_to_clipboard(&self.values, editor, ClipboardType::Clipboard)
@@ -294,6 +823,10 @@ impl Register for PrimaryClipboardRegister {
         read_from_clipboard(&self.values, editor, ClipboardType::Selection)
     }
 
+    fn read_typed<'a>(&'a self, editor: &'a Editor) -> RegisterValuesTyped<'a> {
+        read_typed_from_clipboard(&self.values, editor, ClipboardType::Selection)
+    }
+
     fn write(&mut self, editor: &mut Editor, values: Vec<String>) -> Result<()> {
         self.values = values;
         save_to_clipboard(&self.values, editor, ClipboardType::Selection)
@@ -349,6 +882,35 @@ fn read_from_clipboard<'a>(
     }
 }
 
+/// Like [`read_from_clipboard`], but additionally infers a [`Shape`] for
+/// the clipboard contents (see [`infer_shape`]).
+fn read_typed_from_clipboard<'a>(
+    saved_values: &'a [String],
+    editor: &'a Editor,
+    clipboard_type: ClipboardType,
+) -> RegisterValuesTyped<'a> {
+    let shape = match editor.clipboard_provider.get_contents(clipboard_type) {
+        Ok(contents) => infer_shape(&contents, doc!(editor).line_ending.as_str()),
+        Err(_) => Shape::CharWise,
+    };
+
+    Box::new(
+        read_from_clipboard(saved_values, editor, clipboard_type).map(move |value| (value, shape)),
+    )
+}
+
+/// Infers the [`Shape`] clipboard contents were likely yanked/deleted
+/// with: linewise if the text ends in a trailing line ending (the shape
+/// a whole-line yank/delete would leave behind when joined with
+/// [`save_to_clipboard`]), charwise otherwise.
+fn infer_shape(contents: &str, line_ending: &str) -> Shape {
+    if !contents.is_empty() && contents.ends_with(line_ending) {
+        Shape::LineWise
+    } else {
+        Shape::CharWise
+    }
+}
+
 fn contents_are_saved(saved_values: &[String], editor: &Editor, mut contents: &str) -> bool {
     let line_ending = doc!(editor).line_ending.as_str();
     let mut values = saved_values.iter();
@@ -370,3 +932,119 @@ fn contents_are_saved(saved_values: &[String], editor: &Editor, mut contents: &s
 
     true
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbered_ring_tracks_yanks_in_slot_zero_only() {
+        let mut ring = NumberedRing::default();
+        assert_eq!(ring.get(0), None);
+
+        ring.yanked = Some(("first".to_string(), Shape::CharWise));
+        assert_eq!(ring.get(0), Some("first"));
+
+        ring.yanked = Some(("second".to_string(), Shape::CharWise));
+        assert_eq!(ring.get(0), Some("second"));
+        assert_eq!(ring.get(1), None);
+    }
+
+    #[test]
+    fn numbered_ring_shifts_deletes_onto_the_front() {
+        let mut ring = NumberedRing::default();
+        ring.deleted
+            .push_front(("one".to_string(), Shape::LineWise));
+        assert_eq!(ring.get(1), Some("one"));
+
+        ring.deleted
+            .push_front(("two".to_string(), Shape::LineWise));
+        assert_eq!(ring.get(1), Some("two"));
+        assert_eq!(ring.get(2), Some("one"));
+    }
+
+    #[test]
+    fn numbered_ring_drops_the_oldest_entry_past_capacity() {
+        let mut ring = NumberedRing::default();
+        for i in 0..NUMBERED_RING_CAPACITY {
+            if ring.deleted.len() == NUMBERED_RING_CAPACITY {
+                ring.deleted.pop_back();
+            }
+            ring.deleted
+                .push_front((format!("delete-{i}"), Shape::LineWise));
+        }
+        assert_eq!(ring.deleted.len(), NUMBERED_RING_CAPACITY);
+        assert_eq!(ring.get(9), Some("delete-0"));
+
+        if ring.deleted.len() == NUMBERED_RING_CAPACITY {
+            ring.deleted.pop_back();
+        }
+        ring.deleted
+            .push_front((format!("delete-{NUMBERED_RING_CAPACITY}"), Shape::LineWise));
+
+        assert_eq!(ring.deleted.len(), NUMBERED_RING_CAPACITY);
+        assert_eq!(ring.get(9), Some("delete-1"));
+        assert_eq!(
+            ring.get(1),
+            Some(format!("delete-{NUMBERED_RING_CAPACITY}")).as_deref()
+        );
+    }
+
+    #[test]
+    fn numbered_ring_digit_out_of_range_is_none() {
+        let ring = NumberedRing::default();
+        assert_eq!(ring.get(10), None);
+    }
+
+    #[test]
+    fn numbered_ring_reports_deletes_with_their_own_shape() {
+        let mut ring = NumberedRing::default();
+        ring.deleted
+            .push_front(("col\n".to_string(), Shape::BlockWise));
+        assert_eq!(ring.get_typed(1), Some(("col\n", Shape::BlockWise)));
+    }
+
+    #[test]
+    fn numbered_ring_reports_yanks_with_their_own_shape() {
+        let mut ring = NumberedRing::default();
+        ring.yanked = Some(("col".to_string(), Shape::BlockWise));
+        assert_eq!(ring.get_typed(0), Some(("col", Shape::BlockWise)));
+    }
+
+    #[test]
+    fn infer_shape_reports_linewise_for_trailing_line_ending() {
+        assert_eq!(infer_shape("hello\n", "\n"), Shape::LineWise);
+        assert_eq!(infer_shape("hello\r\n", "\r\n"), Shape::LineWise);
+    }
+
+    #[test]
+    fn infer_shape_reports_charwise_otherwise() {
+        assert_eq!(infer_shape("hello", "\n"), Shape::CharWise);
+        assert_eq!(infer_shape("", "\n"), Shape::CharWise);
+    }
+
+    #[test]
+    fn lowercase_register_name_folds_uppercase_ascii() {
+        assert_eq!(lowercase_register_name('A'), 'a');
+        assert_eq!(lowercase_register_name('Z'), 'z');
+    }
+
+    #[test]
+    fn lowercase_register_name_leaves_other_names_untouched() {
+        assert_eq!(lowercase_register_name('a'), 'a');
+        assert_eq!(lowercase_register_name('1'), '1');
+        assert_eq!(lowercase_register_name('_'), '_');
+    }
+
+    #[test]
+    fn is_small_delete_true_without_a_line_ending() {
+        assert!(is_small_delete("x"));
+        assert!(is_small_delete(""));
+    }
+
+    #[test]
+    fn is_small_delete_false_with_an_embedded_line_ending() {
+        assert!(!is_small_delete("one\ntwo"));
+        assert!(!is_small_delete("whole line\n"));
+    }
+}